@@ -0,0 +1,40 @@
+//! Account iteration helpers for instruction handlers
+
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError};
+
+/// Like `next_account_info`, but logs which account was missing
+pub fn next_named<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    name: &str,
+) -> Result<I::Item, ProgramError> {
+    iter.next().ok_or_else(|| {
+        msg!("Missing account: {}", name);
+        ProgramError::NotEnoughAccountKeys
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::test_utils::{setup_syscall_stubs, take_logs, TestAccount},
+    };
+
+    #[test]
+    fn test_next_named() {
+        setup_syscall_stubs();
+        let mut fixture = TestAccount::new().signer();
+        let key = fixture.key;
+        let accounts = [fixture.info()];
+        let accounts_iter = &mut accounts.iter();
+
+        assert_eq!(next_named(accounts_iter, "initializer").unwrap().key, &key);
+        assert!(take_logs().is_empty());
+
+        assert_eq!(
+            next_named(accounts_iter, "escrow").unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+        assert_eq!(take_logs(), vec!["Missing account: escrow"]);
+    }
+}
@@ -0,0 +1,449 @@
+//! Declarative account validation for instruction handlers
+
+use {
+    crate::error::TemplateError,
+    solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+        pubkey::Pubkey, rent::Rent,
+    },
+};
+
+/// Rejects a zero `amount` argument
+pub fn require_nonzero(amount: u64) -> ProgramResult {
+    if amount == 0 {
+        return Err(TemplateError::AmountZero.into());
+    }
+    Ok(())
+}
+
+/// A single account check
+enum Check<'a, 'b> {
+    Signer(&'a AccountInfo<'b>),
+    SignerOrPda(&'a AccountInfo<'b>, &'a [&'a [u8]], &'a Pubkey),
+    Owner(&'a AccountInfo<'b>, &'a Pubkey),
+    Initialized(&'a AccountInfo<'b>, fn(&[u8]) -> bool),
+    Zeroed(&'a AccountInfo<'b>),
+    Key(&'a AccountInfo<'b>, &'a Pubkey),
+    Distinct(&'a AccountInfo<'b>, &'a AccountInfo<'b>),
+    NotFeePayer(&'a AccountInfo<'b>, &'a AccountInfo<'b>),
+    NotProgram(&'a AccountInfo<'b>, &'a Pubkey),
+    LamportsAtLeast(&'a AccountInfo<'b>, u64),
+    RentExemptAfter(&'a AccountInfo<'b>, u64, &'a Rent),
+}
+
+impl Check<'_, '_> {
+    /// Position in the evaluation order: signer, owner, account data, address,
+    /// balance
+    fn rank(&self) -> u8 {
+        match self {
+            Check::Signer(_) | Check::SignerOrPda(..) => 0,
+            Check::Owner(..) => 1,
+            Check::Initialized(..) | Check::Zeroed(_) => 2,
+            Check::Key(..)
+            | Check::Distinct(..)
+            | Check::NotFeePayer(..)
+            | Check::NotProgram(..) => 3,
+            Check::LamportsAtLeast(..) | Check::RentExemptAfter(..) => 4,
+        }
+    }
+
+    fn run(&self) -> ProgramResult {
+        match self {
+            Check::Signer(account) if !account.is_signer => {
+                Err(ProgramError::MissingRequiredSignature)
+            }
+            Check::SignerOrPda(account, seeds, program_id)
+                if !account.is_signer
+                    && Pubkey::create_program_address(seeds, program_id).as_ref()
+                        != Ok(account.key) =>
+            {
+                Err(ProgramError::InvalidSeeds)
+            }
+            Check::Owner(account, owner) if account.owner != *owner => {
+                Err(ProgramError::IncorrectProgramId)
+            }
+            Check::Initialized(account, is_initialized)
+                if !is_initialized(&account.try_borrow_data()?) =>
+            {
+                Err(ProgramError::UninitializedAccount)
+            }
+            Check::Zeroed(account) if account.try_borrow_data()?.iter().any(|b| *b != 0) => {
+                Err(TemplateError::AccountNotEmpty.into())
+            }
+            Check::Key(account, key) if account.key != *key => {
+                Err(ProgramError::InvalidAccountData)
+            }
+            Check::Distinct(a, b) if a.key == b.key => Err(TemplateError::DuplicateAccount.into()),
+            Check::NotFeePayer(account, fee_payer) if account.key == fee_payer.key => {
+                Err(TemplateError::InvalidFeePayer.into())
+            }
+            Check::NotProgram(account, program_id) if account.key == *program_id => {
+                Err(ProgramError::InvalidAccountData)
+            }
+            Check::LamportsAtLeast(account, amount) if account.lamports() < *amount => {
+                msg!(
+                    "Insufficient funds: {} has {} lamports, needs {}",
+                    account.key,
+                    account.lamports(),
+                    amount
+                );
+                Err(ProgramError::InsufficientFunds)
+            }
+            Check::RentExemptAfter(account, credit, rent)
+                if !rent.is_exempt(
+                    account.lamports().saturating_add(*credit),
+                    account.data_len(),
+                ) =>
+            {
+                Err(TemplateError::RecipientNotRentExempt.into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Collects account checks and runs them in a fixed order, regardless of the
+/// order they were added in, stopping at the first failure
+#[derive(Default)]
+pub struct Checks<'a, 'b> {
+    checks: Vec<Check<'a, 'b>>,
+}
+
+impl<'a, 'b> Checks<'a, 'b> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires `account` to have signed the transaction
+    pub fn signer(mut self, account: &'a AccountInfo<'b>) -> Self {
+        self.checks.push(Check::Signer(account));
+        self
+    }
+
+    /// Requires `account` to have signed the transaction, or to be the
+    /// program address derived from `seeds` and `program_id`, so a PDA that
+    /// can't sign a top-level transaction can still be authorized
+    pub fn signer_or_pda(
+        mut self,
+        account: &'a AccountInfo<'b>,
+        seeds: &'a [&'a [u8]],
+        program_id: &'a Pubkey,
+    ) -> Self {
+        self.checks
+            .push(Check::SignerOrPda(account, seeds, program_id));
+        self
+    }
+
+    /// Requires `account` to be owned by `owner`
+    pub fn owner(mut self, account: &'a AccountInfo<'b>, owner: &'a Pubkey) -> Self {
+        self.checks.push(Check::Owner(account, owner));
+        self
+    }
+
+    /// Requires `is_initialized` to accept the data of `account`
+    pub fn initialized(
+        mut self,
+        account: &'a AccountInfo<'b>,
+        is_initialized: fn(&[u8]) -> bool,
+    ) -> Self {
+        self.checks
+            .push(Check::Initialized(account, is_initialized));
+        self
+    }
+
+    /// Requires every byte of the data of `account` to be zero, as it is in a
+    /// freshly created account
+    pub fn zeroed(mut self, account: &'a AccountInfo<'b>) -> Self {
+        self.checks.push(Check::Zeroed(account));
+        self
+    }
+
+    /// Requires `account` to have the address `key`
+    pub fn key(mut self, account: &'a AccountInfo<'b>, key: &'a Pubkey) -> Self {
+        self.checks.push(Check::Key(account, key));
+        self
+    }
+
+    /// Requires `a` and `b` to be different accounts
+    pub fn distinct(mut self, a: &'a AccountInfo<'b>, b: &'a AccountInfo<'b>) -> Self {
+        self.checks.push(Check::Distinct(a, b));
+        self
+    }
+
+    /// Requires `account`, whose lamports the handler mutates, not to be the
+    /// transaction fee payer `fee_payer`
+    pub fn not_fee_payer(
+        mut self,
+        account: &'a AccountInfo<'b>,
+        fee_payer: &'a AccountInfo<'b>,
+    ) -> Self {
+        self.checks.push(Check::NotFeePayer(account, fee_payer));
+        self
+    }
+
+    /// Requires the payout destination `account` not to be the program itself
+    pub fn not_program(mut self, account: &'a AccountInfo<'b>, program_id: &'a Pubkey) -> Self {
+        self.checks.push(Check::NotProgram(account, program_id));
+        self
+    }
+
+    /// Requires `account` to hold at least `amount` lamports, so a transfer
+    /// out of it fails with a clear error rather than inside a CPI
+    pub fn lamports_at_least(mut self, account: &'a AccountInfo<'b>, amount: u64) -> Self {
+        self.checks.push(Check::LamportsAtLeast(account, amount));
+        self
+    }
+
+    /// Requires `account` to be rent-exempt once it is credited `credit`
+    /// lamports, so a payout can't leave a new recipient below the minimum
+    pub fn rent_exempt_after(
+        mut self,
+        account: &'a AccountInfo<'b>,
+        credit: u64,
+        rent: &'a Rent,
+    ) -> Self {
+        self.checks
+            .push(Check::RentExemptAfter(account, credit, rent));
+        self
+    }
+
+    /// Runs the checks, returning the error of the first one that fails
+    pub fn run(mut self) -> ProgramResult {
+        self.checks.sort_by_key(Check::rank);
+        self.checks.iter().try_for_each(Check::run)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::test_utils::{setup_syscall_stubs, take_logs, TestAccount},
+    };
+
+    fn is_initialized(data: &[u8]) -> bool {
+        data.first() == Some(&1)
+    }
+
+    #[test]
+    fn test_require_nonzero() {
+        assert_eq!(require_nonzero(1), Ok(()));
+        assert_eq!(require_nonzero(u64::MAX), Ok(()));
+        assert_eq!(require_nonzero(0), Err(TemplateError::AmountZero.into()));
+    }
+
+    #[test]
+    fn test_checks_pass() {
+        let mut fixture = TestAccount::new().signer().data(vec![1]);
+        let (key, owner) = (fixture.key, fixture.owner);
+        let account = fixture.info();
+
+        assert_eq!(
+            Checks::new()
+                .key(&account, &key)
+                .initialized(&account, is_initialized)
+                .owner(&account, &owner)
+                .signer(&account)
+                .run(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_checks_short_circuit() {
+        let wrong = Pubkey::new_unique();
+        let mut fixture = TestAccount::new().data(vec![0]);
+        let account = fixture.info();
+
+        // Every check fails; the signer check runs first even when added last
+        assert_eq!(
+            Checks::new()
+                .key(&account, &wrong)
+                .initialized(&account, is_initialized)
+                .owner(&account, &wrong)
+                .signer(&account)
+                .run(),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        assert_eq!(
+            Checks::new()
+                .key(&account, &wrong)
+                .initialized(&account, is_initialized)
+                .owner(&account, &wrong)
+                .run(),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(
+            Checks::new()
+                .key(&account, &wrong)
+                .initialized(&account, is_initialized)
+                .run(),
+            Err(ProgramError::UninitializedAccount)
+        );
+        assert_eq!(
+            Checks::new().key(&account, &wrong).run(),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_checks_signer_or_pda() {
+        let program_id = Pubkey::new_unique();
+        let (pda, bump) = Pubkey::find_program_address(&[b"initializer"], &program_id);
+        let bump = [bump];
+        let seeds: &[&[u8]] = &[b"initializer", &bump];
+        let wrong_seeds: &[&[u8]] = &[b"someone else", &bump];
+
+        let mut pda_fixture = TestAccount::new().key(pda).owner(program_id);
+        let pda_account = pda_fixture.info();
+        assert_eq!(
+            Checks::new()
+                .signer_or_pda(&pda_account, seeds, &program_id)
+                .run(),
+            Ok(())
+        );
+        assert_eq!(
+            Checks::new()
+                .signer_or_pda(&pda_account, wrong_seeds, &program_id)
+                .run(),
+            Err(ProgramError::InvalidSeeds)
+        );
+
+        // A signer is accepted whatever seeds it is paired with
+        let mut signer_fixture = TestAccount::new().signer();
+        let signer = signer_fixture.info();
+        assert_eq!(
+            Checks::new()
+                .signer_or_pda(&signer, wrong_seeds, &program_id)
+                .run(),
+            Ok(())
+        );
+        signer_fixture.is_signer = false;
+        let not_signer = signer_fixture.info();
+        assert_eq!(
+            Checks::new()
+                .signer_or_pda(&not_signer, seeds, &program_id)
+                .run(),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn test_checks_zeroed() {
+        let mut fixture = TestAccount::new().data(vec![0; 41]);
+        let account = fixture.info();
+
+        assert_eq!(Checks::new().zeroed(&account).run(), Ok(()));
+
+        // A stray byte away from any flag still marks the account as used
+        account.try_borrow_mut_data().unwrap()[7] = 0xff;
+        assert_eq!(
+            Checks::new().zeroed(&account).run(),
+            Err(TemplateError::AccountNotEmpty.into())
+        );
+    }
+
+    #[test]
+    fn test_checks_distinct() {
+        let mut fixture = TestAccount::new();
+        let mut other_fixture = TestAccount::new();
+        let account = fixture.info();
+        let other = other_fixture.info();
+
+        assert_eq!(Checks::new().distinct(&account, &other).run(), Ok(()));
+        assert_eq!(
+            Checks::new().distinct(&account, &account).run(),
+            Err(TemplateError::DuplicateAccount.into())
+        );
+        // The same address passed in two account slots is still a duplicate
+        assert_eq!(
+            Checks::new().distinct(&account, &account.clone()).run(),
+            Err(TemplateError::DuplicateAccount.into())
+        );
+    }
+
+    #[test]
+    fn test_checks_not_fee_payer() {
+        let mut fixture = TestAccount::new();
+        let mut payer_fixture = TestAccount::new().owner(Pubkey::default()).signer();
+        let account = fixture.info();
+        let fee_payer = payer_fixture.info();
+
+        assert_eq!(
+            Checks::new().not_fee_payer(&account, &fee_payer).run(),
+            Ok(())
+        );
+        // A client mistakenly paying fees from the program-owned account
+        assert_eq!(
+            Checks::new()
+                .not_fee_payer(&account, &account.clone())
+                .run(),
+            Err(TemplateError::InvalidFeePayer.into())
+        );
+    }
+
+    #[test]
+    fn test_checks_not_program() {
+        let mut taker_fixture = TestAccount::new();
+        let mut program_fixture = TestAccount::new().executable();
+        let program_id = program_fixture.key;
+        let taker = taker_fixture.info();
+        let program = program_fixture.info();
+
+        assert_eq!(Checks::new().not_program(&taker, &program_id).run(), Ok(()));
+        assert_eq!(
+            Checks::new().not_program(&program, &program_id).run(),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_checks_lamports_at_least() {
+        setup_syscall_stubs();
+        let mut fixture = TestAccount::new().signer().lamports(100);
+        let key = fixture.key;
+        let account = fixture.info();
+
+        assert_eq!(Checks::new().lamports_at_least(&account, 100).run(), Ok(()));
+        assert!(take_logs().is_empty());
+
+        assert_eq!(
+            Checks::new().lamports_at_least(&account, 101).run(),
+            Err(ProgramError::InsufficientFunds)
+        );
+        assert_eq!(
+            take_logs(),
+            vec![format!(
+                "Insufficient funds: {} has 100 lamports, needs 101",
+                key
+            )]
+        );
+    }
+
+    #[test]
+    fn test_checks_rent_exempt_after() {
+        let rent = Rent::default();
+        let minimum = rent.minimum_balance(0);
+        let mut fixture = TestAccount::new();
+        let account = fixture.info();
+
+        assert_eq!(
+            Checks::new()
+                .rent_exempt_after(&account, minimum - 1, &rent)
+                .run(),
+            Err(TemplateError::RecipientNotRentExempt.into())
+        );
+        assert_eq!(
+            Checks::new()
+                .rent_exempt_after(&account, minimum, &rent)
+                .run(),
+            Ok(())
+        );
+
+        **account.lamports.borrow_mut() = minimum;
+        assert_eq!(
+            Checks::new().rent_exempt_after(&account, 1, &rent).run(),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Hash algorithm selection for claim codes
+
+use {
+    crate::error::TemplateError,
+    solana_program::{hash, keccak, program_error::ProgramError},
+};
+
+/// Algorithm a claim-code digest was produced with, stored as one byte
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashAlgo {
+    Sha256,
+    Keccak256,
+}
+
+impl TryFrom<u8> for HashAlgo {
+    type Error = ProgramError;
+
+    fn try_from(algo: u8) -> Result<Self, Self::Error> {
+        match algo {
+            0 => Ok(Self::Sha256),
+            1 => Ok(Self::Keccak256),
+            _ => Err(TemplateError::UnsupportedHashAlgo.into()),
+        }
+    }
+}
+
+impl HashAlgo {
+    pub fn hash(self, preimage: &[u8]) -> [u8; 32] {
+        match self {
+            Self::Sha256 => hash::hash(preimage).to_bytes(),
+            Self::Keccak256 => keccak::hash(preimage).to_bytes(),
+        }
+    }
+
+    /// Returns whether `preimage` hashes to `digest`
+    pub fn verify(self, preimage: &[u8], digest: &[u8; 32]) -> bool {
+        self.hash(preimage) == *digest
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_hash_algo_try_from() {
+        assert_eq!(HashAlgo::try_from(0), Ok(HashAlgo::Sha256));
+        assert_eq!(HashAlgo::try_from(1), Ok(HashAlgo::Keccak256));
+        assert_eq!(
+            HashAlgo::try_from(2),
+            Err(TemplateError::UnsupportedHashAlgo.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_sha256() {
+        let digest = HashAlgo::Sha256.hash(b"abc");
+        assert_eq!(
+            to_hex(&digest),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(HashAlgo::Sha256.verify(b"abc", &digest));
+        assert!(!HashAlgo::Sha256.verify(b"abd", &digest));
+        assert!(!HashAlgo::Keccak256.verify(b"abc", &digest));
+    }
+
+    #[test]
+    fn test_verify_keccak256() {
+        let digest = HashAlgo::Keccak256.hash(b"abc");
+        assert_eq!(
+            to_hex(&digest),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+        assert!(HashAlgo::Keccak256.verify(b"abc", &digest));
+        assert!(!HashAlgo::Keccak256.verify(b"abd", &digest));
+        assert!(!HashAlgo::Sha256.verify(b"abc", &digest));
+    }
+}
@@ -0,0 +1,28 @@
+//! Error types
+
+use solana_program::program_error::ProgramError;
+
+/// Errors that may be returned by the program
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TemplateError {
+    /// An amount argument was zero
+    AmountZero,
+    /// A payout would leave its recipient below the rent-exempt minimum
+    RecipientNotRentExempt,
+    /// The same account was passed in two slots that must differ
+    DuplicateAccount,
+    /// A hash algorithm byte did not name a supported algorithm
+    UnsupportedHashAlgo,
+    /// An account expected to be freshly created already holds data
+    AccountNotEmpty,
+    /// A program-owned account being mutated is also the transaction fee payer
+    InvalidFeePayer,
+    /// The result did not fit in a `u64`, or a division by zero was attempted
+    ArithmeticOverflow,
+}
+
+impl From<TemplateError> for ProgramError {
+    fn from(e: TemplateError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,31 @@
+//! Human-readable formatting for log messages
+
+use solana_program::native_token::LAMPORTS_PER_SOL;
+
+/// Formats a lamport amount as SOL without going through floating point,
+/// e.g. `1_500_000_000` becomes `"1.5"` and `1` becomes `"0.000000001"`.
+pub fn lamports_to_sol_string(lamports: u64) -> String {
+    let sol = lamports / LAMPORTS_PER_SOL;
+    let fraction = lamports % LAMPORTS_PER_SOL;
+    if fraction == 0 {
+        return sol.to_string();
+    }
+    let fraction = format!("{:09}", fraction);
+    format!("{}.{}", sol, fraction.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lamports_to_sol_string() {
+        assert_eq!(lamports_to_sol_string(0), "0");
+        assert_eq!(lamports_to_sol_string(1), "0.000000001");
+        assert_eq!(lamports_to_sol_string(500_000_000), "0.5");
+        assert_eq!(lamports_to_sol_string(LAMPORTS_PER_SOL), "1");
+        assert_eq!(lamports_to_sol_string(1_500_000_000), "1.5");
+        assert_eq!(lamports_to_sol_string(2_000_000_010), "2.00000001");
+        assert_eq!(lamports_to_sol_string(u64::MAX), "18446744073.709551615");
+    }
+}
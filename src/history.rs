@@ -0,0 +1,172 @@
+//! Ring buffer of recent exchange records
+
+use solana_program::{
+    program_error::ProgramError,
+    program_pack::{Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// One settled exchange
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExchangeRecord {
+    pub taker: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+impl ExchangeRecord {
+    const LEN: usize = 32 + 8 + 8;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        dst[..32].copy_from_slice(self.taker.as_ref());
+        dst[32..40].copy_from_slice(&self.amount.to_le_bytes());
+        dst[40..48].copy_from_slice(&self.timestamp.to_le_bytes());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Self {
+        let mut amount = [0; 8];
+        let mut timestamp = [0; 8];
+        amount.copy_from_slice(&src[32..40]);
+        timestamp.copy_from_slice(&src[40..48]);
+        Self {
+            taker: Pubkey::new(&src[..32]),
+            amount: u64::from_le_bytes(amount),
+            timestamp: i64::from_le_bytes(timestamp),
+        }
+    }
+}
+
+/// The last `CAPACITY` exchange records, packed as the index of the next slot
+/// to write, the number of records stored, then every slot in slot order.
+/// Once full, each new record overwrites the oldest one.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct HistoryBuffer {
+    next: u8,
+    len: u8,
+    records: [ExchangeRecord; Self::CAPACITY],
+}
+
+impl HistoryBuffer {
+    pub const CAPACITY: usize = 8;
+
+    /// Appends `record`, overwriting the oldest record once the buffer is full
+    pub fn push(&mut self, record: ExchangeRecord) {
+        self.records[self.next as usize] = record;
+        self.next = ((self.next as usize + 1) % Self::CAPACITY) as u8;
+        self.len = (self.len as usize + 1).min(Self::CAPACITY) as u8;
+    }
+
+    /// Returns the stored records from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &ExchangeRecord> {
+        let start = self.next as usize + Self::CAPACITY - self.len as usize;
+        (0..self.len as usize).map(move |i| &self.records[(start + i) % Self::CAPACITY])
+    }
+}
+
+impl Sealed for HistoryBuffer {}
+
+impl Pack for HistoryBuffer {
+    const LEN: usize = 2 + Self::CAPACITY * ExchangeRecord::LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        dst[0] = self.next;
+        dst[1] = self.len;
+        for (record, dst) in self
+            .records
+            .iter()
+            .zip(dst[2..].chunks_exact_mut(ExchangeRecord::LEN))
+        {
+            record.pack_into_slice(dst);
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let (next, len) = (src[0], src[1]);
+        // Until the buffer wraps, records fill the slots from zero
+        let full = len as usize == Self::CAPACITY;
+        if next as usize >= Self::CAPACITY
+            || len as usize > Self::CAPACITY
+            || (!full && next != len)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut records = [ExchangeRecord::default(); Self::CAPACITY];
+        for (record, src) in records
+            .iter_mut()
+            .zip(src[2..].chunks_exact(ExchangeRecord::LEN))
+        {
+            *record = ExchangeRecord::unpack_from_slice(src);
+        }
+        Ok(Self { next, len, records })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(amount: u64) -> ExchangeRecord {
+        ExchangeRecord {
+            taker: Pubkey::new_unique(),
+            amount,
+            timestamp: amount as i64 * 60,
+        }
+    }
+
+    #[test]
+    fn test_push_before_wrap() {
+        let mut history = HistoryBuffer::default();
+        assert_eq!(history.iter().count(), 0);
+
+        let (first, second) = (record(1), record(2));
+        history.push(first);
+        history.push(second);
+        assert_eq!(
+            history.iter().copied().collect::<Vec<_>>(),
+            vec![first, second]
+        );
+    }
+
+    #[test]
+    fn test_push_wraps_around() {
+        let mut history = HistoryBuffer::default();
+        let records = (0..HistoryBuffer::CAPACITY as u64 + 3)
+            .map(record)
+            .collect::<Vec<_>>();
+        for record in &records {
+            history.push(*record);
+        }
+
+        // Only the most recent CAPACITY records remain, oldest first
+        assert_eq!(
+            history.iter().copied().collect::<Vec<_>>(),
+            records[3..].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let mut history = HistoryBuffer::default();
+        for amount in 0..HistoryBuffer::CAPACITY as u64 + 3 {
+            history.push(record(amount));
+        }
+
+        let mut dst = vec![0; HistoryBuffer::LEN];
+        HistoryBuffer::pack(history, &mut dst).unwrap();
+        assert_eq!(&dst[..2], &[3, HistoryBuffer::CAPACITY as u8]);
+        assert_eq!(HistoryBuffer::unpack_unchecked(&dst), Ok(history));
+    }
+
+    #[test]
+    fn test_unpack_invalid_header() {
+        let mut src = vec![0; HistoryBuffer::LEN];
+        for header in [[8, 8], [0, 9], [1, 3]] {
+            src[..2].copy_from_slice(&header);
+            assert_eq!(
+                HistoryBuffer::unpack_unchecked(&src),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+    }
+}
@@ -1,3 +1,24 @@
+#[allow(dead_code)]
+mod accounts;
+#[allow(dead_code)]
+mod checks;
+#[allow(dead_code)]
+mod digest;
+#[allow(dead_code)]
+mod error;
+#[allow(dead_code)]
+mod format;
+#[allow(dead_code)]
+mod history;
+#[allow(dead_code)]
+mod math;
+#[allow(dead_code)]
+mod maybe_pubkey;
+#[allow(dead_code)]
+mod rent;
+#[cfg(test)]
+mod test_utils;
+
 use solana_program::{
     account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg, pubkey::Pubkey,
 };
@@ -8,12 +29,18 @@ fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    #[cfg(feature = "profiling")]
+    solana_program::log::sol_log_compute_units();
+
     msg!(
         "process_instruction: {}: {} accounts, data={:?}",
         program_id,
         accounts.len(),
         instruction_data
     );
+
+    #[cfg(feature = "profiling")]
+    solana_program::log::sol_log_compute_units();
     Ok(())
 }
 
@@ -21,6 +48,7 @@ fn process_instruction(
 mod test {
     use {
         super::*,
+        crate::test_utils::TestAccount,
         assert_matches::*,
         solana_program::instruction::{AccountMeta, Instruction},
         solana_program_test::*,
@@ -51,4 +79,17 @@ mod test {
 
         assert_matches!(banks_client.process_transaction(transaction).await, Ok(()));
     }
+
+    #[test]
+    fn test_process_instruction_direct() {
+        let program_id = Pubkey::new_unique();
+        let mut payer = TestAccount::new().signer().lamports(1_000_000);
+        let accounts = [payer.info()];
+
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &[1, 2, 3]),
+            Ok(())
+        );
+        assert_eq!(**accounts[0].lamports.borrow(), 1_000_000);
+    }
 }
@@ -0,0 +1,54 @@
+//! Checked lamport arithmetic helpers
+
+use {crate::error::TemplateError, solana_program::program_error::ProgramError};
+
+/// Computes `a + b`
+pub fn add(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_add(b)
+        .ok_or_else(|| TemplateError::ArithmeticOverflow.into())
+}
+
+/// Computes `a - b`
+pub fn sub(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_sub(b)
+        .ok_or_else(|| TemplateError::ArithmeticOverflow.into())
+}
+
+/// Computes `a * num / den`, rounding down, without overflowing on the
+/// intermediate product
+pub fn mul_div(a: u64, num: u64, den: u64) -> Result<u64, ProgramError> {
+    (a as u128)
+        .checked_mul(num as u128)
+        .and_then(|product| product.checked_div(den as u128))
+        .and_then(|quotient| u64::try_from(quotient).ok())
+        .ok_or_else(|| TemplateError::ArithmeticOverflow.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const OVERFLOW: ProgramError = ProgramError::Custom(6);
+
+    #[test]
+    fn test_add() {
+        assert_eq!(add(1, 2), Ok(3));
+        assert_eq!(add(u64::MAX - 1, 1), Ok(u64::MAX));
+        assert_eq!(add(u64::MAX, 1), Err(OVERFLOW));
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(sub(3, 2), Ok(1));
+        assert_eq!(sub(2, 2), Ok(0));
+        assert_eq!(sub(1, 2), Err(OVERFLOW));
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(1_000, 25, 10_000), Ok(2));
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX), Ok(u64::MAX));
+        assert_eq!(mul_div(u64::MAX, 2, 1), Err(OVERFLOW));
+        assert_eq!(mul_div(1, 1, 0), Err(OVERFLOW));
+    }
+}
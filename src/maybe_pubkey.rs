@@ -0,0 +1,106 @@
+//! Pubkey field that may be an explicit wildcard
+
+use solana_program::{
+    program_error::ProgramError,
+    program_pack::{Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Either a specific key or a wildcard accepting any key, packed as a
+/// presence byte followed by the 32 key bytes (all zero for the wildcard)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MaybePubkey {
+    Any,
+    Key(Pubkey),
+}
+
+impl MaybePubkey {
+    /// Returns whether `key` is accepted
+    pub fn matches(&self, key: &Pubkey) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Key(expected) => expected == key,
+        }
+    }
+}
+
+impl Sealed for MaybePubkey {}
+
+impl Pack for MaybePubkey {
+    const LEN: usize = 33;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let (tag, key) = dst.split_at_mut(1);
+        match self {
+            Self::Any => {
+                tag[0] = 0;
+                key.fill(0);
+            }
+            Self::Key(pubkey) => {
+                tag[0] = 1;
+                key.copy_from_slice(pubkey.as_ref());
+            }
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, key) = src.split_at(1);
+        match tag[0] {
+            0 if key.iter().all(|b| *b == 0) => Ok(Self::Any),
+            1 => Ok(Self::Key(Pubkey::new(key))),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pack_wildcard() {
+        let mut dst = [0xff; MaybePubkey::LEN];
+        MaybePubkey::pack(MaybePubkey::Any, &mut dst).unwrap();
+        assert_eq!(dst, [0; MaybePubkey::LEN]);
+        assert_eq!(MaybePubkey::unpack_unchecked(&dst), Ok(MaybePubkey::Any));
+        assert!(MaybePubkey::Any.matches(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_pack_key() {
+        let key = Pubkey::new_unique();
+        let mut dst = [0; MaybePubkey::LEN];
+        MaybePubkey::pack(MaybePubkey::Key(key), &mut dst).unwrap();
+        assert_eq!(dst[0], 1);
+        assert_eq!(&dst[1..], key.as_ref());
+        assert_eq!(
+            MaybePubkey::unpack_unchecked(&dst),
+            Ok(MaybePubkey::Key(key))
+        );
+        assert!(MaybePubkey::Key(key).matches(&key));
+        assert!(!MaybePubkey::Key(key).matches(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_unpack_invalid() {
+        let mut src = [0; MaybePubkey::LEN];
+        src[0] = 2;
+        assert_eq!(
+            MaybePubkey::unpack_unchecked(&src),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // A wildcard must not carry key bytes
+        src[0] = 0;
+        src[1] = 1;
+        assert_eq!(
+            MaybePubkey::unpack_unchecked(&src),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        assert_eq!(
+            MaybePubkey::unpack_unchecked(&src[..MaybePubkey::LEN - 1]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
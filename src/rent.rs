@@ -0,0 +1,42 @@
+//! Rent sysvar access
+
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, rent::Rent, sysvar::Sysvar,
+};
+
+/// Reads rent from `rent_sysvar` when the caller passed the account, and from
+/// the `Rent::get` syscall otherwise. A passed account whose key is not the
+/// rent sysvar id is rejected with `InvalidArgument`.
+pub fn get_rent(rent_sysvar: Option<&AccountInfo>) -> Result<Rent, ProgramError> {
+    match rent_sysvar {
+        Some(account) => Rent::from_account_info(account),
+        None => Rent::get(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::test_utils::{setup_syscall_stubs, TestAccount, TEST_RENT},
+        solana_program::{pubkey::Pubkey, sysvar},
+    };
+
+    #[test]
+    fn test_get_rent() {
+        setup_syscall_stubs();
+        let mut fixture = TestAccount::new()
+            .key(sysvar::rent::id())
+            .owner(sysvar::id())
+            .data(vec![0; Rent::size_of()]);
+        let mut account = fixture.info();
+        TEST_RENT.to_account_info(&mut account).unwrap();
+
+        assert_eq!(get_rent(None), Ok(TEST_RENT));
+        assert_eq!(get_rent(Some(&account)), get_rent(None));
+
+        let wrong_key = Pubkey::new_unique();
+        account.key = &wrong_key;
+        assert_eq!(get_rent(Some(&account)), Err(ProgramError::InvalidArgument));
+    }
+}
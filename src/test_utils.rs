@@ -0,0 +1,118 @@
+//! Shared helpers for unit tests
+
+use {
+    solana_program::{
+        account_info::AccountInfo,
+        entrypoint::SUCCESS,
+        program_stubs::{set_syscall_stubs, SyscallStubs},
+        pubkey::Pubkey,
+        rent::Rent,
+    },
+    std::{cell::RefCell, sync::Once},
+};
+
+/// Rent served by the stubbed rent sysvar, deliberately not `Rent::default()`
+pub const TEST_RENT: Rent = Rent {
+    lamports_per_byte_year: 1,
+    exemption_threshold: 2.0,
+    burn_percent: 3,
+};
+
+thread_local! {
+    static LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns and clears the messages logged so far on the current test thread
+pub fn take_logs() -> Vec<String> {
+    LOGS.with(|logs| logs.take())
+}
+
+struct TestSyscallStubs;
+
+impl SyscallStubs for TestSyscallStubs {
+    fn sol_log(&self, message: &str) {
+        LOGS.with(|logs| logs.borrow_mut().push(message.to_string()));
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = TEST_RENT };
+        SUCCESS
+    }
+}
+
+/// Installs `TestSyscallStubs` once for the whole test binary
+pub fn setup_syscall_stubs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        set_syscall_stubs(Box::new(TestSyscallStubs));
+    });
+}
+
+/// Owns the key, lamports and data behind an `AccountInfo`, so handlers and
+/// checks can be called synchronously without `ProgramTest`
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    /// A writable, non-signer account with unique key and owner, no lamports
+    /// and no data
+    pub fn new() -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            lamports: 0,
+            data: vec![],
+            is_signer: false,
+            executable: false,
+        }
+    }
+
+    pub fn key(mut self, key: Pubkey) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self
+    }
+
+    pub fn signer(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    pub fn executable(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+}
@@ -34,3 +34,48 @@ fn test_validator_transaction() {
 
     assert_matches!(rpc_client.send_and_confirm_transaction(&transaction), Ok(_));
 }
+
+/// Starts a validator running the program and signs a transaction invoking it
+#[cfg(feature = "profiling")]
+fn setup_validator_transaction() -> (TestValidator, Transaction) {
+    solana_logger::setup_with_default("solana_program_runtime=debug");
+    let program_id = Pubkey::new_unique();
+
+    let (test_validator, payer) = TestValidatorGenesis::default()
+        .add_program("bpf_program_template", program_id)
+        .start();
+
+    let blockhash = test_validator
+        .get_rpc_client()
+        .get_latest_blockhash()
+        .unwrap();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(payer.pubkey(), false)],
+            data: vec![1, 2, 3],
+        }],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], blockhash);
+
+    (test_validator, transaction)
+}
+
+#[cfg(feature = "profiling")]
+#[test]
+fn test_validator_transaction_logs_compute_units() {
+    let (test_validator, transaction) = setup_validator_transaction();
+    let rpc_client = test_validator.get_rpc_client();
+
+    let result = rpc_client.simulate_transaction(&transaction).unwrap().value;
+    assert_matches!(result.err, None);
+    let compute_logs = result
+        .logs
+        .unwrap()
+        .iter()
+        .filter(|log| log.starts_with("Program consumption: "))
+        .count();
+    assert_eq!(compute_logs, 2);
+}